@@ -0,0 +1,43 @@
+// SPDX-FileCopyrightText: 2023 Geosiris
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Avro binary (de)serialization for generated ETP datatypes.
+//!
+//! Every record and enum generated from the ETP v12 Avro schemas implements
+//! [`EtpAvro`], giving callers a single, uniform way to turn a typed ETP
+//! value into the Avro binary payload carried over the WebSocket transport
+//! and back again.
+
+use apache_avro::types::Value;
+use apache_avro::{from_avro_datum, from_value, to_avro_datum, to_value, Schema};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::EtpError;
+
+/// Implemented by every type generated from an ETP v12 Avro schema.
+pub trait EtpAvro: Sized + Serialize + DeserializeOwned {
+    /// The canonical JSON Avro schema this type was generated from.
+    const SCHEMA: &'static str;
+
+    /// Parses [`Self::SCHEMA`] into an [`apache_avro::Schema`].
+    fn schema() -> Result<Schema, EtpError> {
+        Schema::parse_str(Self::SCHEMA).map_err(EtpError::Schema)
+    }
+
+    /// Encodes `self` as an ETP Avro binary payload (a single Avro datum,
+    /// with no object container file framing).
+    fn to_avro(&self) -> Result<Vec<u8>, EtpError> {
+        let schema = Self::schema()?;
+        let value: Value = to_value(self)?;
+        to_avro_datum(&schema, value).map_err(EtpError::Avro)
+    }
+
+    /// Decodes an ETP Avro binary payload produced by [`Self::to_avro`].
+    fn from_avro(bytes: &[u8]) -> Result<Self, EtpError> {
+        let schema = Self::schema()?;
+        let mut reader = bytes;
+        let value = from_avro_datum(&schema, &mut reader, None)?;
+        from_value(&value).map_err(EtpError::Avro)
+    }
+}
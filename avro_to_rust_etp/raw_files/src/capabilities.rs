@@ -0,0 +1,195 @@
+// SPDX-FileCopyrightText: 2023 Geosiris
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Typed endpoint-capability negotiation.
+//!
+//! [`EndpointCapabilityKind`] only names a capability; the value ETP
+//! actually negotiates in the `RequestSession`/`OpenSession` handshake is a
+//! `map<DataValue>` plus an optional unit per entry. [`Capabilities`] is
+//! that map, with typed getters/setters, the per-key validation the
+//! protocol mandates, and the min/max merge rule used to compute the
+//! effective capability set from a local and a remote map.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::energistics::etp::v12::datatypes::data_value::DataValue;
+use crate::energistics::etp::v12::datatypes::endpoint_capability_kind::EndpointCapabilityKind;
+
+/// A single negotiated capability: its value plus the unit it is expressed
+/// in, when the capability is a physical quantity (e.g. a duration).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapabilityValue {
+    pub value: DataValue,
+    pub unit: Option<String>,
+}
+
+impl CapabilityValue {
+    pub fn new(value: DataValue) -> Self {
+        CapabilityValue { value, unit: None }
+    }
+
+    pub fn with_unit(value: DataValue, unit: impl Into<String>) -> Self {
+        CapabilityValue {
+            value,
+            unit: Some(unit.into()),
+        }
+    }
+}
+
+/// An error returned when a capability value does not satisfy the
+/// protocol-defined constraints for its [`EndpointCapabilityKind`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapabilityValidationError {
+    pub kind: EndpointCapabilityKind,
+    pub reason: String,
+}
+
+/// An error returned while parsing a `map<DataValue>` capability map
+/// received from a peer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CapabilityWireError {
+    /// The map contained a key that is not a known `EndpointCapabilityKind`.
+    UnknownCapability(String),
+    /// A known capability's value failed validation.
+    Validation(CapabilityValidationError),
+}
+
+impl From<CapabilityValidationError> for CapabilityWireError {
+    fn from(e: CapabilityValidationError) -> Self {
+        CapabilityWireError::Validation(e)
+    }
+}
+
+/// A negotiated set of ETP endpoint capabilities, as carried in
+/// `RequestSession.endpointCapabilities` / `OpenSession.endpointCapabilities`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Capabilities {
+    values: HashMap<EndpointCapabilityKind, CapabilityValue>,
+}
+
+impl Capabilities {
+    /// Creates an empty capability set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the capability value for `kind`, if set.
+    pub fn get(&self, kind: &EndpointCapabilityKind) -> Option<&CapabilityValue> {
+        self.values.get(kind)
+    }
+
+    /// Returns the capability value for `kind`, falling back to the
+    /// protocol-defined default (see [`default_value`]) when it has not
+    /// been negotiated. Returns `None` if `kind` has neither a negotiated
+    /// value nor a spec-defined default.
+    pub fn get_or_default(&self, kind: &EndpointCapabilityKind) -> Option<CapabilityValue> {
+        self.values
+            .get(kind)
+            .cloned()
+            .or_else(|| default_value(kind))
+    }
+
+    /// Sets `kind` to `value`, validating it first.
+    pub fn set(
+        &mut self,
+        kind: EndpointCapabilityKind,
+        value: CapabilityValue,
+    ) -> Result<(), CapabilityValidationError> {
+        validate(&kind, &value)?;
+        self.values.insert(kind, value);
+        Ok(())
+    }
+
+    /// Computes the effective capability set negotiated between `local` and
+    /// `remote`. For a key both sides set, two integer values of the same
+    /// kind (`Long`/`Int`) merge to the smaller one, whatever the key (for
+    /// the `Max*` resource ceilings that is the more restrictive value); two
+    /// booleans merge to `true` only if both are `true`; any other pair
+    /// keeps the local value. A key set on one side only keeps that value.
+    pub fn merge(local: &Capabilities, remote: &Capabilities) -> Capabilities {
+        let mut merged = local.clone();
+        for (kind, remote_value) in &remote.values {
+            let effective = match local.values.get(kind) {
+                Some(local_value) => merge_value(local_value, remote_value),
+                None => remote_value.clone(),
+            };
+            merged.values.insert(kind.clone(), effective);
+        }
+        merged
+    }
+
+    /// Converts this capability set to the `map<DataValue>` shape ETP
+    /// expects in the `RequestSession`/`OpenSession` handshake. The unit an
+    /// entry may carry locally is not part of that wire shape and is
+    /// dropped.
+    pub fn to_wire(&self) -> HashMap<String, DataValue> {
+        self.values
+            .iter()
+            .map(|(kind, value)| (kind.to_string(), value.value.clone()))
+            .collect()
+    }
+
+    /// Parses a `map<DataValue>` as received in the handshake, validating
+    /// every entry against the protocol-defined constraints for its key.
+    pub fn from_wire(map: &HashMap<String, DataValue>) -> Result<Self, CapabilityWireError> {
+        let mut capabilities = Capabilities::new();
+        for (name, value) in map {
+            let kind = EndpointCapabilityKind::from_str(name)
+                .map_err(|_| CapabilityWireError::UnknownCapability(name.clone()))?;
+            capabilities.set(kind, CapabilityValue::new(value.clone()))?;
+        }
+        Ok(capabilities)
+    }
+}
+
+/// The protocol-defined default for capabilities ETP specifies a fallback
+/// for when a peer does not negotiate them explicitly.
+fn default_value(kind: &EndpointCapabilityKind) -> Option<CapabilityValue> {
+    match kind {
+        EndpointCapabilityKind::ActiveTimeoutPeriod => Some(CapabilityValue::new(DataValue::Long(60))),
+        EndpointCapabilityKind::MaxWebSocketMessagePayloadSize => {
+            Some(CapabilityValue::new(DataValue::Long(10_000_000)))
+        }
+        EndpointCapabilityKind::MaxWebSocketFramePayloadSize => {
+            Some(CapabilityValue::new(DataValue::Long(4_000_000)))
+        }
+        _ => None,
+    }
+}
+
+fn merge_value(local: &CapabilityValue, remote: &CapabilityValue) -> CapabilityValue {
+    match (&local.value, &remote.value) {
+        (DataValue::Long(l), DataValue::Long(r)) => CapabilityValue {
+            value: DataValue::Long((*l).min(*r)),
+            unit: local.unit.clone(),
+        },
+        (DataValue::Int(l), DataValue::Int(r)) => CapabilityValue {
+            value: DataValue::Int((*l).min(*r)),
+            unit: local.unit.clone(),
+        },
+        (DataValue::Boolean(l), DataValue::Boolean(r)) => CapabilityValue {
+            value: DataValue::Boolean(*l && *r),
+            unit: local.unit.clone(),
+        },
+        _ => local.clone(),
+    }
+}
+
+fn validate(
+    kind: &EndpointCapabilityKind,
+    value: &CapabilityValue,
+) -> Result<(), CapabilityValidationError> {
+    match kind {
+        EndpointCapabilityKind::MaxWebSocketMessagePayloadSize
+        | EndpointCapabilityKind::MaxWebSocketFramePayloadSize => match &value.value {
+            DataValue::Long(v) if *v > 0 => Ok(()),
+            DataValue::Int(v) if *v > 0 => Ok(()),
+            _ => Err(CapabilityValidationError {
+                kind: kind.clone(),
+                reason: format!("{:?} must be a positive integer", kind),
+            }),
+        },
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,37 @@
+// SPDX-FileCopyrightText: 2023 Geosiris
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::fmt;
+
+/// Errors raised while encoding, decoding or otherwise handling ETP Avro
+/// data.
+#[derive(Debug)]
+pub enum EtpError {
+    /// The canonical Avro schema carried on a generated type failed to
+    /// parse.
+    Schema(apache_avro::Error),
+    /// Encoding or decoding an Avro binary payload failed.
+    Avro(apache_avro::Error),
+    /// No type is registered for the given schema fingerprint.
+    UnknownFingerprint(u64),
+}
+
+impl fmt::Display for EtpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EtpError::Schema(e) => write!(f, "invalid Avro schema: {}", e),
+            EtpError::Avro(e) => write!(f, "Avro encode/decode error: {}", e),
+            EtpError::UnknownFingerprint(fp) => {
+                write!(f, "no type registered for schema fingerprint {:#018x}", fp)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EtpError {}
+
+impl From<apache_avro::Error> for EtpError {
+    fn from(e: apache_avro::Error) -> Self {
+        EtpError::Avro(e)
+    }
+}
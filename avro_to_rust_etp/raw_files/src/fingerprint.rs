@@ -0,0 +1,112 @@
+// SPDX-FileCopyrightText: 2023 Geosiris
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! CRC-64-AVRO (Rabin) schema fingerprints.
+//!
+//! ETP identifies the schema of a message body by its 64-bit Avro "Rabin"
+//! fingerprint (see the
+//! [Avro spec](https://avro.apache.org/docs/current/specification/#schema-fingerprints)).
+//! Every [`EtpAvro`] type gets this fingerprint for free via
+//! [`EtpFingerprint`], computed from the Avro Parsing Canonical Form of its
+//! schema using `apache_avro`'s own [`apache_avro::rabin::Rabin`] digest, so
+//! receivers can dispatch an incoming ETP frame to the right Rust type purely
+//! from the fingerprint carried on the wire.
+
+use std::any::Any;
+use std::collections::HashMap;
+
+use apache_avro::rabin::Rabin;
+use digest::Digest;
+
+use crate::avro_trait::EtpAvro;
+use crate::error::EtpError;
+
+/// Computes the CRC-64-AVRO (Rabin) fingerprint of `bytes` using
+/// `apache_avro`'s own [`Rabin`] digest, so this stays in lockstep with
+/// whatever fingerprint `Schema::fingerprint::<Rabin>()` produces rather than
+/// maintaining a parallel hand-rolled implementation of the same algorithm.
+pub fn rabin_fingerprint(bytes: &[u8]) -> u64 {
+    let mut hasher = Rabin::default();
+    hasher.update(bytes);
+    let digest = hasher.finalize();
+    u64::from_le_bytes(digest.as_slice().try_into().expect("Rabin digest is 8 bytes"))
+}
+
+/// Implemented by every [`EtpAvro`] type, giving it a schema fingerprint
+/// derived from its Avro Parsing Canonical Form.
+pub trait EtpFingerprint: EtpAvro {
+    /// The CRC-64-AVRO fingerprint of this type's canonical schema form.
+    fn avro_fingerprint() -> Result<u64, EtpError> {
+        let schema = Self::schema()?;
+        let fingerprint = schema.fingerprint::<Rabin>();
+        Ok(u64::from_le_bytes(
+            fingerprint
+                .bytes
+                .as_slice()
+                .try_into()
+                .expect("Rabin fingerprint is 8 bytes"),
+        ))
+    }
+}
+
+impl<T: EtpAvro> EtpFingerprint for T {}
+
+/// Object-safe facade over [`EtpAvro`] so a decoded value can be stored as a
+/// trait object (its concrete type is only known at the registration call
+/// site) while still being re-encodable and downcastable by callers such as
+/// [`crate::message::Message`].
+pub trait ErasedEtpAvro: Any + Send + Sync {
+    /// Re-encodes the erased value as Avro binary.
+    fn encode_erased(&self) -> Result<Vec<u8>, EtpError>;
+    /// Returns `self` as `&dyn Any`, for downcasting back to the concrete
+    /// [`EtpAvro`] type.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: EtpAvro + Send + Sync + 'static> ErasedEtpAvro for T {
+    fn encode_erased(&self) -> Result<Vec<u8>, EtpError> {
+        self.to_avro()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+type Decoder = fn(&[u8]) -> Result<Box<dyn ErasedEtpAvro>, EtpError>;
+
+/// Maps schema fingerprints to the decoder of the [`EtpAvro`] type they were
+/// computed from, so an incoming ETP frame can be dispatched to the right
+/// Rust type purely from the fingerprint carried on the wire.
+#[derive(Default)]
+pub struct FingerprintRegistry {
+    decoders: HashMap<u64, Decoder>,
+}
+
+impl FingerprintRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `T` under its [`EtpFingerprint::avro_fingerprint`].
+    pub fn register<T>(&mut self) -> Result<(), EtpError>
+    where
+        T: EtpFingerprint + Send + Sync + 'static,
+    {
+        let fingerprint = T::avro_fingerprint()?;
+        self.decoders.insert(fingerprint, |bytes| {
+            T::from_avro(bytes).map(|v| Box::new(v) as Box<dyn ErasedEtpAvro>)
+        });
+        Ok(())
+    }
+
+    /// Decodes `bytes` using the decoder registered for `fingerprint`.
+    pub fn decode(&self, fingerprint: u64, bytes: &[u8]) -> Result<Box<dyn ErasedEtpAvro>, EtpError> {
+        let decoder = self
+            .decoders
+            .get(&fingerprint)
+            .ok_or(EtpError::UnknownFingerprint(fingerprint))?;
+        decoder(bytes)
+    }
+}
@@ -0,0 +1,73 @@
+// SPDX-FileCopyrightText: 2023 Geosiris
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Multi-version ETP message surface.
+//!
+//! `energistics::etp::v12` is the only schema generation this crate ships
+//! today, so [`Message`] has a single `V12` variant; it exists so a second
+//! `v1X` generation can be added later without callers having to hard-code
+//! which version a decoded message came from. [`Message::decode`] dispatches
+//! on the schema fingerprint carried by the frame (see
+//! [`crate::fingerprint::FingerprintRegistry`]), so any registered type can
+//! be decoded, and tags the result `V12`; [`Message::encode`] emits the schema
+//! version the peer actually negotiated, and [`Message::into_latest`] is the
+//! seam a future version's field defaults/renames would upgrade through.
+
+use crate::error::EtpError;
+use crate::fingerprint::{ErasedEtpAvro, FingerprintRegistry};
+
+/// An ETP schema version a peer can negotiate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EtpVersion {
+    V12,
+}
+
+/// A decoded ETP message body, tagged with the schema version it was
+/// decoded against.
+pub enum Message {
+    /// Decoded against an `energistics::etp::v12` schema.
+    V12(Box<dyn ErasedEtpAvro>),
+}
+
+impl Message {
+    /// Decodes `bytes` using whichever type `registry` has registered for
+    /// `fingerprint`, tagging the result with the version that type belongs
+    /// to.
+    pub fn decode(
+        fingerprint: u64,
+        bytes: &[u8],
+        registry: &FingerprintRegistry,
+    ) -> Result<Self, EtpError> {
+        let body = registry.decode(fingerprint, bytes)?;
+        Ok(Message::V12(body))
+    }
+
+    /// Downcasts the decoded body to `T`, regardless of which version
+    /// variant it is tagged with.
+    pub fn downcast<T: 'static>(&self) -> Option<&T> {
+        match self {
+            Message::V12(body) => body.as_any().downcast_ref::<T>(),
+        }
+    }
+
+    /// Encodes this message as Avro binary for `target_version`, the ETP
+    /// schema version the peer negotiated. Only v12 is supported today, so
+    /// this always emits the body as-is when `target_version` is
+    /// [`EtpVersion::V12`]; a future version would downgrade the body to
+    /// that version's schema here instead of erroring.
+    pub fn encode(&self, target_version: EtpVersion) -> Result<Vec<u8>, EtpError> {
+        match (self, target_version) {
+            (Message::V12(body), EtpVersion::V12) => body.encode_erased(),
+        }
+    }
+
+    /// Upgrades this message to the newest schema version this crate
+    /// supports, applying whatever field defaults and renames that version
+    /// introduced. Only ETP v12 is supported today, so this is currently
+    /// the identity conversion.
+    pub fn into_latest(self) -> Message {
+        match self {
+            Message::V12(body) => Message::V12(body),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+// SPDX-FileCopyrightText: 2023 Geosiris
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Reader/writer Avro schema compatibility checking.
+//!
+//! ETP peers can run against slightly different revisions of the same
+//! schema (e.g. a newer server talking to an older client library). Before
+//! trusting that a message can be decoded, the two sides' schemas should be
+//! checked for Avro schema resolution compatibility; this module implements
+//! that check and reports a structured diff instead of a bare bool, so
+//! callers can log or surface exactly what would break.
+
+use apache_avro::schema::{Alias, Name, Schema, UnionSchema};
+
+/// One reason a reader schema cannot resolve a value written with a writer
+/// schema, at a given path inside the schema tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incompatibility {
+    /// Dotted path to the offending field/branch, rooted at `$`, e.g.
+    /// `"$.capabilities.value"`.
+    pub path: String,
+    /// Human-readable description of the mismatch.
+    pub reason: String,
+}
+
+impl Incompatibility {
+    fn new(path: &str, reason: impl Into<String>) -> Self {
+        Incompatibility {
+            path: path.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Checks whether `reader` can resolve values written with `writer`,
+/// returning every incompatibility found. An empty result means the schemas
+/// are compatible.
+pub fn check_compatibility(reader: &Schema, writer: &Schema) -> Vec<Incompatibility> {
+    let mut incompatibilities = Vec::new();
+    let mut ancestors = Vec::new();
+    check_at(reader, writer, "$", &mut ancestors, &mut incompatibilities);
+    incompatibilities
+}
+
+/// Identifies a `(reader, writer)` schema pair by pointer for cycle
+/// detection, mirroring how `apache_avro`'s own internal schema resolution
+/// tracks visited pairs to cope with self-/mutually-recursive records.
+fn schema_ptr(schema: &Schema) -> usize {
+    schema as *const Schema as usize
+}
+
+fn check_at(
+    reader: &Schema,
+    writer: &Schema,
+    path: &str,
+    ancestors: &mut Vec<(usize, usize)>,
+    out: &mut Vec<Incompatibility>,
+) {
+    let pair = (schema_ptr(reader), schema_ptr(writer));
+    if ancestors.contains(&pair) {
+        // This exact (reader, writer) pair is already being checked further
+        // up the call stack, so we hit this point through a recursive
+        // (self- or mutually-referential) schema. The ancestor call will
+        // report any incompatibility it finds; recursing again here would
+        // loop forever instead of terminating.
+        return;
+    }
+    ancestors.push(pair);
+
+    match (reader, writer) {
+        (Schema::Record(r), Schema::Record(w)) => {
+            if !names_compatible(&r.name, &r.aliases, &w.name) {
+                out.push(Incompatibility::new(
+                    path,
+                    format!(
+                        "reader record `{}` does not match writer record `{}` by name or alias",
+                        r.name, w.name
+                    ),
+                ));
+            } else {
+                for field in &r.fields {
+                    let matched = w.fields.iter().find(|wf| {
+                        wf.name == field.name
+                            || field.aliases.as_ref().is_some_and(|a| a.contains(&wf.name))
+                    });
+                    match matched {
+                        Some(writer_field) => {
+                            let field_path = format!("{}.{}", path, field.name);
+                            check_at(&field.schema, &writer_field.schema, &field_path, ancestors, out);
+                        }
+                        None if field.default.is_some() => {}
+                        None => out.push(Incompatibility::new(
+                            path,
+                            format!(
+                                "reader field `{}` has no matching writer field and no default",
+                                field.name
+                            ),
+                        )),
+                    }
+                }
+            }
+        }
+        (Schema::Enum(r), Schema::Enum(w)) => {
+            if !names_compatible(&r.name, &r.aliases, &w.name) {
+                out.push(Incompatibility::new(
+                    path,
+                    format!(
+                        "reader enum `{}` does not match writer enum `{}` by name or alias",
+                        r.name, w.name
+                    ),
+                ));
+            } else {
+                let missing: Vec<_> = w
+                    .symbols
+                    .iter()
+                    .filter(|s| !r.symbols.contains(s))
+                    .cloned()
+                    .collect();
+                if !missing.is_empty() && r.default.is_none() {
+                    out.push(Incompatibility::new(
+                        path,
+                        format!(
+                            "reader enum is missing writer symbols {:?} and has no default symbol",
+                            missing
+                        ),
+                    ));
+                }
+            }
+        }
+        (Schema::Fixed(r), Schema::Fixed(w)) => {
+            if !names_compatible(&r.name, &r.aliases, &w.name) {
+                out.push(Incompatibility::new(
+                    path,
+                    format!(
+                        "reader fixed `{}` does not match writer fixed `{}` by name or alias",
+                        r.name, w.name
+                    ),
+                ));
+            } else if r.size != w.size {
+                out.push(Incompatibility::new(
+                    path,
+                    format!(
+                        "reader fixed `{}` has size {} but writer fixed `{}` has size {}",
+                        r.name, r.size, w.name, w.size
+                    ),
+                ));
+            }
+        }
+        (Schema::Union(r), Schema::Union(w)) => {
+            for (i, writer_branch) in w.variants().iter().enumerate() {
+                if !resolves_any(r, writer_branch) {
+                    out.push(Incompatibility::new(
+                        &format!("{}[{}]", path, i),
+                        "no reader union branch matches this writer branch",
+                    ));
+                }
+            }
+        }
+        (reader_schema, Schema::Union(w)) => {
+            for (i, writer_branch) in w.variants().iter().enumerate() {
+                let branch_path = format!("{}[{}]", path, i);
+                check_at(reader_schema, writer_branch, &branch_path, ancestors, out);
+            }
+        }
+        (Schema::Union(r), writer_schema) => {
+            if !resolves_any(r, writer_schema) {
+                out.push(Incompatibility::new(path, "no reader union branch matches the writer schema"));
+            }
+        }
+        (Schema::Array(r), Schema::Array(w)) => {
+            check_at(r, w, &format!("{}[]", path), ancestors, out);
+        }
+        (Schema::Map(r), Schema::Map(w)) => {
+            check_at(r, w, &format!("{}{{}}", path), ancestors, out);
+        }
+        (reader_prim, writer_prim) if reader_prim == writer_prim => {}
+        (reader_prim, writer_prim) if widens(writer_prim, reader_prim) => {}
+        (reader_schema, writer_schema) => out.push(Incompatibility::new(
+            path,
+            format!(
+                "incompatible types: reader is {:?}, writer is {:?}",
+                reader_schema, writer_schema
+            ),
+        )),
+    }
+
+    ancestors.pop();
+}
+
+/// Whether `writer_name` resolves against the reader's own name or one of
+/// its declared aliases, as Avro's named-schema resolution requires: a
+/// reader and writer record/enum/fixed must refer to the same named type
+/// (directly or through an alias) before their internals are compared,
+/// otherwise two unrelated types with coincidentally matching structure
+/// would be reported as compatible.
+fn names_compatible(reader_name: &Name, reader_aliases: &Option<Vec<Alias>>, writer_name: &Name) -> bool {
+    reader_name.fullname(None) == writer_name.fullname(None)
+        || reader_aliases.as_ref().is_some_and(|aliases| {
+            aliases
+                .iter()
+                .any(|alias| alias.fullname(None) == writer_name.fullname(None))
+        })
+}
+
+fn resolves_any(union: &UnionSchema, writer_branch: &Schema) -> bool {
+    union
+        .variants()
+        .iter()
+        .any(|reader_branch| check_compatibility(reader_branch, writer_branch).is_empty())
+}
+
+/// Avro's promotion rules: a writer value of `from` can always be read as
+/// `to` without loss of applicability (int -> long -> float -> double,
+/// string <-> bytes).
+fn widens(from: &Schema, to: &Schema) -> bool {
+    matches!(
+        (from, to),
+        (Schema::Int, Schema::Long)
+            | (Schema::Int, Schema::Float)
+            | (Schema::Int, Schema::Double)
+            | (Schema::Long, Schema::Float)
+            | (Schema::Long, Schema::Double)
+            | (Schema::Float, Schema::Double)
+            | (Schema::String, Schema::Bytes)
+            | (Schema::Bytes, Schema::String)
+    )
+}
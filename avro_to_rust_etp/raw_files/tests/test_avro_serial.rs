@@ -0,0 +1,13 @@
+// SPDX-FileCopyrightText: 2023 Geosiris
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use etptypes::avro_trait::EtpAvro;
+use etptypes::energistics::etp::v12::datatypes::endpoint_capability_kind::EndpointCapabilityKind;
+
+#[test]
+fn test_avro_round_trip() {
+    for cap_kind in EndpointCapabilityKind::iter() {
+        let bytes = cap_kind.to_avro().unwrap();
+        assert_eq!(*cap_kind, EndpointCapabilityKind::from_avro(&bytes).unwrap());
+    }
+}
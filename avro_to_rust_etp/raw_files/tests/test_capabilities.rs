@@ -0,0 +1,95 @@
+// SPDX-FileCopyrightText: 2023 Geosiris
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use etptypes::capabilities::{CapabilityValue, Capabilities};
+use etptypes::energistics::etp::v12::datatypes::data_value::DataValue;
+use etptypes::energistics::etp::v12::datatypes::endpoint_capability_kind::EndpointCapabilityKind;
+
+#[test]
+fn test_set_and_get() {
+    let mut caps = Capabilities::new();
+    caps.set(
+        EndpointCapabilityKind::MaxWebSocketMessagePayloadSize,
+        CapabilityValue::new(DataValue::Long(1024)),
+    )
+    .unwrap();
+
+    assert_eq!(
+        caps.get(&EndpointCapabilityKind::MaxWebSocketMessagePayloadSize)
+            .unwrap()
+            .value,
+        DataValue::Long(1024)
+    );
+}
+
+#[test]
+fn test_non_positive_payload_size_is_rejected() {
+    let mut caps = Capabilities::new();
+    let err = caps
+        .set(
+            EndpointCapabilityKind::MaxWebSocketMessagePayloadSize,
+            CapabilityValue::new(DataValue::Long(0)),
+        )
+        .unwrap_err();
+    assert_eq!(err.kind, EndpointCapabilityKind::MaxWebSocketMessagePayloadSize);
+}
+
+#[test]
+fn test_merge_takes_min_for_resource_limits() {
+    let mut local = Capabilities::new();
+    local
+        .set(
+            EndpointCapabilityKind::MaxWebSocketMessagePayloadSize,
+            CapabilityValue::new(DataValue::Long(4096)),
+        )
+        .unwrap();
+
+    let mut remote = Capabilities::new();
+    remote
+        .set(
+            EndpointCapabilityKind::MaxWebSocketMessagePayloadSize,
+            CapabilityValue::new(DataValue::Long(2048)),
+        )
+        .unwrap();
+
+    let merged = Capabilities::merge(&local, &remote);
+    assert_eq!(
+        merged
+            .get(&EndpointCapabilityKind::MaxWebSocketMessagePayloadSize)
+            .unwrap()
+            .value,
+        DataValue::Long(2048)
+    );
+}
+
+#[test]
+fn test_wire_round_trip() {
+    let mut caps = Capabilities::new();
+    caps.set(
+        EndpointCapabilityKind::MaxWebSocketMessagePayloadSize,
+        CapabilityValue::new(DataValue::Long(1024)),
+    )
+    .unwrap();
+
+    let wire = caps.to_wire();
+    let parsed = Capabilities::from_wire(&wire).unwrap();
+    assert_eq!(parsed, caps);
+}
+
+#[test]
+fn test_from_wire_rejects_unknown_capability() {
+    let mut wire = std::collections::HashMap::new();
+    wire.insert("NotARealCapability".to_string(), DataValue::Boolean(true));
+    assert!(Capabilities::from_wire(&wire).is_err());
+}
+
+#[test]
+fn test_get_or_default_falls_back_to_spec_default() {
+    let caps = Capabilities::new();
+    assert_eq!(
+        caps.get_or_default(&EndpointCapabilityKind::ActiveTimeoutPeriod)
+            .unwrap()
+            .value,
+        DataValue::Long(60)
+    );
+}
@@ -0,0 +1,33 @@
+// SPDX-FileCopyrightText: 2023 Geosiris
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use etptypes::avro_trait::EtpAvro;
+use etptypes::energistics::etp::v12::datatypes::endpoint_capability_kind::EndpointCapabilityKind;
+use etptypes::fingerprint::{rabin_fingerprint, EtpFingerprint, FingerprintRegistry};
+
+
+#[test]
+fn test_fingerprint_is_stable() {
+    let first = EndpointCapabilityKind::avro_fingerprint().unwrap();
+    let second = EndpointCapabilityKind::avro_fingerprint().unwrap();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_empty_fingerprint_matches_spec() {
+    // The Avro spec's worked example: the fingerprint of the empty byte
+    // string is the CRC-64-AVRO "EMPTY" constant itself.
+    assert_eq!(rabin_fingerprint(&[]), 0xc15d213aa4d7a795);
+}
+
+#[test]
+fn test_registry_dispatches_by_fingerprint() {
+    let mut registry = FingerprintRegistry::new();
+    registry.register::<EndpointCapabilityKind>().unwrap();
+
+    let fingerprint = EndpointCapabilityKind::avro_fingerprint().unwrap();
+    let bytes = EndpointCapabilityKind::iter().next().unwrap().to_avro().unwrap();
+
+    let decoded = registry.decode(fingerprint, &bytes).unwrap();
+    assert!(decoded.as_any().downcast_ref::<EndpointCapabilityKind>().is_some());
+}
@@ -0,0 +1,36 @@
+// SPDX-FileCopyrightText: 2023 Geosiris
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use etptypes::avro_trait::EtpAvro;
+use etptypes::energistics::etp::v12::datatypes::endpoint_capability_kind::EndpointCapabilityKind;
+use etptypes::fingerprint::{EtpFingerprint, FingerprintRegistry};
+use etptypes::message::{EtpVersion, Message};
+
+#[test]
+fn test_decode_dispatches_to_v12_and_round_trips() {
+    let mut registry = FingerprintRegistry::new();
+    registry.register::<EndpointCapabilityKind>().unwrap();
+
+    let sent = EndpointCapabilityKind::iter().next().unwrap();
+    let fingerprint = EndpointCapabilityKind::avro_fingerprint().unwrap();
+    let bytes = sent.to_avro().unwrap();
+
+    let message = Message::decode(fingerprint, &bytes, &registry).unwrap();
+    assert_eq!(message.downcast::<EndpointCapabilityKind>(), Some(sent));
+
+    let latest = message.into_latest();
+    assert_eq!(latest.downcast::<EndpointCapabilityKind>(), Some(sent));
+}
+
+#[test]
+fn test_encode_emits_negotiated_version() {
+    let mut registry = FingerprintRegistry::new();
+    registry.register::<EndpointCapabilityKind>().unwrap();
+
+    let sent = EndpointCapabilityKind::iter().next().unwrap();
+    let fingerprint = EndpointCapabilityKind::avro_fingerprint().unwrap();
+    let bytes = sent.to_avro().unwrap();
+
+    let message = Message::decode(fingerprint, &bytes, &registry).unwrap();
+    assert_eq!(message.encode(EtpVersion::V12).unwrap(), bytes);
+}
@@ -0,0 +1,82 @@
+// SPDX-FileCopyrightText: 2023 Geosiris
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use apache_avro::Schema;
+use etptypes::schema_compatibility::check_compatibility;
+
+#[test]
+fn test_identical_schemas_are_compatible() {
+    let schema = Schema::parse_str(r#"{"type": "string"}"#).unwrap();
+    assert!(check_compatibility(&schema, &schema).is_empty());
+}
+
+#[test]
+fn test_widening_promotion_is_compatible() {
+    let reader = Schema::parse_str(r#"{"type": "long"}"#).unwrap();
+    let writer = Schema::parse_str(r#"{"type": "int"}"#).unwrap();
+    assert!(check_compatibility(&reader, &writer).is_empty());
+}
+
+#[test]
+fn test_missing_field_without_default_is_incompatible() {
+    let reader = Schema::parse_str(
+        r#"{"type": "record", "name": "R", "fields": [{"name": "a", "type": "string"}]}"#,
+    )
+    .unwrap();
+    let writer = Schema::parse_str(r#"{"type": "record", "name": "R", "fields": []}"#).unwrap();
+    let incompatibilities = check_compatibility(&reader, &writer);
+    assert_eq!(incompatibilities.len(), 1);
+}
+
+#[test]
+fn test_missing_field_with_default_is_compatible() {
+    let reader = Schema::parse_str(
+        r#"{"type": "record", "name": "R", "fields": [{"name": "a", "type": "string", "default": ""}]}"#,
+    )
+    .unwrap();
+    let writer = Schema::parse_str(r#"{"type": "record", "name": "R", "fields": []}"#).unwrap();
+    assert!(check_compatibility(&reader, &writer).is_empty());
+}
+
+#[test]
+fn test_unrelated_records_with_same_fields_are_incompatible() {
+    let reader = Schema::parse_str(
+        r#"{"type": "record", "name": "Foo", "fields": [{"name": "a", "type": "string"}]}"#,
+    )
+    .unwrap();
+    let writer = Schema::parse_str(
+        r#"{"type": "record", "name": "Bar", "fields": [{"name": "a", "type": "string"}]}"#,
+    )
+    .unwrap();
+    let incompatibilities = check_compatibility(&reader, &writer);
+    assert_eq!(incompatibilities.len(), 1);
+}
+
+#[test]
+fn test_record_matches_writer_by_alias() {
+    let reader = Schema::parse_str(
+        r#"{"type": "record", "name": "Foo", "aliases": ["Bar"], "fields": [{"name": "a", "type": "string"}]}"#,
+    )
+    .unwrap();
+    let writer = Schema::parse_str(
+        r#"{"type": "record", "name": "Bar", "fields": [{"name": "a", "type": "string"}]}"#,
+    )
+    .unwrap();
+    assert!(check_compatibility(&reader, &writer).is_empty());
+}
+
+#[test]
+fn test_self_recursive_record_does_not_overflow() {
+    let schema = Schema::parse_str(
+        r#"{
+            "type": "record",
+            "name": "LinkedNode",
+            "fields": [
+                {"name": "value", "type": "string"},
+                {"name": "next", "type": ["null", "LinkedNode"], "default": null}
+            ]
+        }"#,
+    )
+    .unwrap();
+    assert!(check_compatibility(&schema, &schema).is_empty());
+}